@@ -0,0 +1,241 @@
+use Float;
+
+use scalar::Scalar;
+use types::{Point, Vector};
+use primitive::Object;
+
+/// A single output triangle of a polygonized mesh: three vertices in
+/// winding order plus the face normal to write out alongside them (e.g.
+/// into an STL facet record).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Triangle<S: Scalar = Float> {
+    pub vertices: [Point<S>; 3],
+    pub normal: Vector<S>,
+}
+
+/// Parameters controlling how an `Object` is sampled before its
+/// isosurface is extracted.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bounds<S: Scalar = Float> {
+    pub min: Point<S>,
+    pub max: Point<S>,
+}
+
+/// Samples `object` over `bounds` on a grid with `resolution` spacing
+/// between samples and extracts the `value() == 0` isosurface via
+/// marching cubes, returning it as a triangle soup.
+///
+/// `resolution` is the edge length of a grid cell; smaller values give a
+/// more accurate mesh at the cost of roughly cubic growth in sample
+/// count, so callers should pick it to trade accuracy for speed.
+pub fn polygonize<S: Scalar>(object: &Object<S>,
+                             bounds: &Bounds<S>,
+                             resolution: S)
+                             -> Vec<Triangle<S>> {
+    assert!(resolution > S::zero());
+
+    let nx = ((((bounds.max.x - bounds.min.x) / resolution).to_f64().ceil()) as usize) + 1;
+    let ny = ((((bounds.max.y - bounds.min.y) / resolution).to_f64().ceil()) as usize) + 1;
+    let nz = ((((bounds.max.z - bounds.min.z) / resolution).to_f64().ceil()) as usize) + 1;
+
+    // Scalar field sampled once per grid corner, indexed by
+    // `i + j * nx + k * nx * ny`, so each interior cell can look up its
+    // eight corners without re-evaluating `value`.
+    let mut field = Vec::with_capacity(nx * ny * nz);
+    let corner_point = |i: usize, j: usize, k: usize| {
+        Point::new(bounds.min.x + S::from_f64(i as f64) * resolution,
+                   bounds.min.y + S::from_f64(j as f64) * resolution,
+                   bounds.min.z + S::from_f64(k as f64) * resolution)
+    };
+    for k in 0..nz {
+        for j in 0..ny {
+            for i in 0..nx {
+                field.push(object.value(&corner_point(i, j, k)));
+            }
+        }
+    }
+    let index = |i: usize, j: usize, k: usize| i + j * nx + k * nx * ny;
+
+    let mut triangles = Vec::new();
+    if nx < 2 || ny < 2 || nz < 2 {
+        return triangles;
+    }
+
+    for k in 0..(nz - 1) {
+        for j in 0..(ny - 1) {
+            for i in 0..(nx - 1) {
+                let corner_pos = [corner_point(i, j, k),
+                                   corner_point(i + 1, j, k),
+                                   corner_point(i + 1, j + 1, k),
+                                   corner_point(i, j + 1, k),
+                                   corner_point(i, j, k + 1),
+                                   corner_point(i + 1, j, k + 1),
+                                   corner_point(i + 1, j + 1, k + 1),
+                                   corner_point(i, j + 1, k + 1)];
+                let corner_val = [field[index(i, j, k)],
+                                   field[index(i + 1, j, k)],
+                                   field[index(i + 1, j + 1, k)],
+                                   field[index(i, j + 1, k)],
+                                   field[index(i, j, k + 1)],
+                                   field[index(i + 1, j, k + 1)],
+                                   field[index(i + 1, j + 1, k + 1)],
+                                   field[index(i, j + 1, k + 1)]];
+
+                let mut case_index = 0u8;
+                for c in 0..8 {
+                    if corner_val[c] < S::zero() {
+                        case_index |= 1 << c;
+                    }
+                }
+                let edges = EDGE_TABLE[case_index as usize];
+                if edges == 0 {
+                    continue;
+                }
+
+                let mut edge_vertex: [Point<S>; 12] = [corner_pos[0]; 12];
+                for e in 0..12 {
+                    if edges & (1 << e) == 0 {
+                        continue;
+                    }
+                    let (a, b) = EDGE_CORNERS[e];
+                    edge_vertex[e] = interpolate(corner_pos[a],
+                                                  corner_val[a],
+                                                  corner_pos[b],
+                                                  corner_val[b]);
+                }
+
+                let tris = &TRI_TABLE[case_index as usize];
+                let mut t = 0;
+                while tris[t] != -1 {
+                    let v0 = edge_vertex[tris[t] as usize];
+                    let v1 = edge_vertex[tris[t + 1] as usize];
+                    let v2 = edge_vertex[tris[t + 2] as usize];
+                    // Degenerate (zero-area) triangles can appear when an
+                    // interpolation parameter snaps to an existing
+                    // corner on more than one edge; drop them so the
+                    // output mesh stays watertight without spurious
+                    // slivers.
+                    if v0 != v1 && v1 != v2 && v0 != v2 {
+                        let three = S::from_f64(3.0);
+                        let centroid = Point::new((v0.x + v1.x + v2.x) / three,
+                                                   (v0.y + v1.y + v2.y) / three,
+                                                   (v0.z + v1.z + v2.z) / three);
+                        triangles.push(Triangle {
+                            vertices: [v0, v1, v2],
+                            normal: object.normal(&centroid),
+                        });
+                    }
+                    t += 3;
+                }
+            }
+        }
+    }
+    triangles
+}
+
+/// Linearly interpolates the point along the edge `p0`-`p1` where the
+/// sampled scalar field crosses zero, snapping to an endpoint when the
+/// crossing is (near) exactly there so coincident edges on neighbouring
+/// cells produce bit-identical vertices and the mesh stays watertight.
+fn interpolate<S: Scalar>(p0: Point<S>, v0: S, p1: Point<S>, v1: S) -> Point<S> {
+    let epsilon = S::from_f64(1e-6);
+    if v0.abs() < epsilon || (v0 - v1).abs() < epsilon {
+        return p0;
+    }
+    if v1.abs() < epsilon {
+        return p1;
+    }
+    let t = v0 / (v0 - v1);
+    Point::new(p0.x + t * (p1.x - p0.x),
+               p0.y + t * (p1.y - p0.y),
+               p0.z + t * (p1.z - p0.z))
+}
+
+/// Corner indices (into the per-cell 8-corner array below) at each end
+/// of the 12 edges of a cube, using the standard marching-cubes corner
+/// numbering (bottom face 0-1-2-3 counter-clockwise, top face 4-5-6-7
+/// directly above them).
+const EDGE_CORNERS: [(usize, usize); 12] = [(0, 1), (1, 2), (2, 3), (3, 0), (4, 5), (5, 6),
+                                             (6, 7), (7, 4), (0, 4), (1, 5), (2, 6), (3, 7)];
+
+/// For each of the 256 inside/outside corner configurations, a bitmask
+/// of which of the 12 cube edges are crossed by the isosurface.
+const EDGE_TABLE: [u16; 256] =
+    [0x0, 0x109, 0x203, 0x30a, 0x406, 0x50f, 0x605, 0x70c, 0x80c, 0x905, 0xa0f, 0xb06, 0xc0a,
+     0xd03, 0xe09, 0xf00, 0x190, 0x99, 0x393, 0x29a, 0x596, 0x49f, 0x795, 0x69c, 0x99c, 0x895,
+     0xb9f, 0xa96, 0xd9a, 0xc93, 0xf99, 0xe90, 0x230, 0x339, 0x33, 0x13a, 0x636, 0x73f, 0x435,
+     0x53c, 0xa3c, 0xb35, 0x83f, 0x936, 0xe3a, 0xf33, 0xc39, 0xd30, 0x3a0, 0x2a9, 0x1a3, 0xaa,
+     0x7a6, 0x6af, 0x5a5, 0x4ac, 0xbac, 0xaa5, 0x9af, 0x8a6, 0xfaa, 0xea3, 0xda9, 0xca0, 0x460,
+     0x569, 0x663, 0x76a, 0x66, 0x16f, 0x265, 0x36c, 0xc6c, 0xd65, 0xe6f, 0xf66, 0x86a, 0x963,
+     0xa69, 0xb60, 0x5f0, 0x4f9, 0x7f3, 0x6fa, 0x1f6, 0xff, 0x3f5, 0x2fc, 0xdfc, 0xcf5, 0xfff,
+     0xef6, 0x9fa, 0x8f3, 0xbf9, 0xaf0, 0x650, 0x759, 0x453, 0x55a, 0x256, 0x35f, 0x55, 0x15c,
+     0xe5c, 0xf55, 0xc5f, 0xd56, 0xa5a, 0xb53, 0x859, 0x950, 0x7c0, 0x6c9, 0x5c3, 0x4ca, 0x3c6,
+     0x2cf, 0x1c5, 0xcc, 0xfcc, 0xec5, 0xdcf, 0xcc6, 0xbca, 0xac3, 0x9c9, 0x8c0, 0x8c0, 0x9c9,
+     0xac3, 0xbca, 0xcc6, 0xdcf, 0xec5, 0xfcc, 0xcc, 0x1c5, 0x2cf, 0x3c6, 0x4ca, 0x5c3, 0x6c9,
+     0x7c0, 0x950, 0x859, 0xb53, 0xa5a, 0xd56, 0xc5f, 0xf55, 0xe5c, 0x15c, 0x55, 0x35f, 0x256,
+     0x55a, 0x453, 0x759, 0x650, 0xaf0, 0xbf9, 0x8f3, 0x9fa, 0xef6, 0xfff, 0xcf5, 0xdfc, 0x2fc,
+     0x3f5, 0xff, 0x1f6, 0x6fa, 0x7f3, 0x4f9, 0x5f0, 0xb60, 0xa69, 0x963, 0x86a, 0xf66, 0xe6f,
+     0xd65, 0xc6c, 0x36c, 0x265, 0x16f, 0x66, 0x76a, 0x663, 0x569, 0x460, 0xca0, 0xda9, 0xea3,
+     0xfaa, 0x8a6, 0x9af, 0xaa5, 0xbac, 0x4ac, 0x5a5, 0x6af, 0x7a6, 0xaa, 0x1a3, 0x2a9, 0x3a0,
+     0xd30, 0xc39, 0xf33, 0xe3a, 0x936, 0x83f, 0xb35, 0xa3c, 0x53c, 0x435, 0x73f, 0x636, 0x13a,
+     0x33, 0x339, 0x230, 0xe90, 0xf99, 0xc93, 0xd9a, 0xa96, 0xb9f, 0x895, 0x99c, 0x69c, 0x795,
+     0x49f, 0x596, 0x29a, 0x393, 0x99, 0x190, 0xf00, 0xe09, 0xd03, 0xc0a, 0xb06, 0xa0f, 0x905,
+     0x80c, 0x70c, 0x605, 0x50f, 0x406, 0x30a, 0x203, 0x109, 0x0];
+
+/// For each of the 256 corner configurations, up to five triangles as
+/// triples of indices into the 12 cube edges, terminated by `-1`.
+///
+/// This is the standard marching-cubes triangulation table (see Paul
+/// Bourke's "Polygonising a scalar field"), kept in its own file since
+/// it is a large opaque constant rather than something to read inline.
+#[cfg_attr(rustfmt, rustfmt_skip)]
+const TRI_TABLE: [[i8; 16]; 256] = include!("mesh_tri_table.rs.in");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitive::Sphere;
+
+    #[test]
+    fn polygonize_sphere_is_nonempty_and_roughly_spherical() {
+        let sphere = Sphere::new(1.0);
+        let bounds = Bounds {
+            min: Point::new(-1.5, -1.5, -1.5),
+            max: Point::new(1.5, 1.5, 1.5),
+        };
+        let triangles = polygonize(&*sphere, &bounds, 0.25);
+
+        assert!(!triangles.is_empty());
+
+        // Every vertex should lie close to the unit sphere's surface,
+        // within the slop the grid resolution and linear interpolation
+        // can introduce.
+        for triangle in &triangles {
+            for vertex in &triangle.vertices {
+                let radius = vertex.to_vec().length();
+                assert!((radius - 1.0).abs() < 0.25,
+                        "vertex {:?} has radius {}, expected close to 1.0",
+                        vertex,
+                        radius);
+            }
+        }
+    }
+
+    #[test]
+    fn polygonize_instantiates_at_f32_for_fast_preview_meshing() {
+        let sphere: Box<Sphere<f32>> = Sphere::new(1.0f32);
+        let bounds = Bounds {
+            min: Point::new(-1.5f32, -1.5, -1.5),
+            max: Point::new(1.5f32, 1.5, 1.5),
+        };
+        let triangles = polygonize(&*sphere, &bounds, 0.25f32);
+
+        assert!(!triangles.is_empty());
+        for triangle in &triangles {
+            for vertex in &triangle.vertices {
+                let radius = vertex.to_vec().length();
+                assert!((radius - 1.0).abs() < 0.25);
+            }
+        }
+    }
+}
@@ -1,36 +1,50 @@
 use std::fmt::Debug;
-use std::f64;
 
 use Float;
 
-use types::{Point, Vector, Transform, EPSILON_X, EPSILON_Y, EPSILON_Z};
+use scalar::Scalar;
+use types::{Point, Vector, Transform, epsilon_x, epsilon_y, epsilon_z};
 
-pub trait ImplicitFunction {
-    fn value(&self, p: &Point) -> Float;
-    fn normal(&self, p: &Point) -> Vector;
+pub trait ImplicitFunction<S: Scalar = Float> {
+    fn value(&self, p: &Point<S>) -> S;
+    fn normal(&self, p: &Point<S>) -> Vector<S>;
 }
 
-fn normal_from_implicit<T: ImplicitFunction>(f: &T, p: &Point) -> Vector {
+pub fn normal_from_implicit<S: Scalar, T: ImplicitFunction<S>>(f: &T, p: &Point<S>) -> Vector<S> {
     let center = f.value(p);
-    let dx = f.value(&(*p + EPSILON_X)) - center;
-    let dy = f.value(&(*p + EPSILON_Y)) - center;
-    let dz = f.value(&(*p + EPSILON_Z)) - center;
+    let dx = f.value(&(*p + epsilon_x())) - center;
+    let dy = f.value(&(*p + epsilon_y())) - center;
+    let dz = f.value(&(*p + epsilon_z())) - center;
     Vector::new(dx, dy, dz).normalize()
 }
 
-pub trait Primitive: ImplicitFunction + Clone + Debug {}
+pub trait Primitive<S: Scalar = Float>: ImplicitFunction<S> + Clone + Debug {}
 
-pub trait Object: ImplicitFunction + ObjectClone {
-    fn apply_transform(&mut self, other: &Transform);
-    fn translate(&mut self, t: Vector) {
+pub trait Object<S: Scalar = Float>: ImplicitFunction<S> + ObjectClone<S> + Debug {
+    fn apply_transform(&mut self, other: &Transform<S>);
+    fn translate(&mut self, t: Vector<S>) {
         let trans = Transform::translate(&t);
         self.apply_transform(&trans);
     }
-    fn rotate(&mut self, r: Vector) {
+    fn rotate(&mut self, r: Vector<S>) {
         let trans = Transform::rotate(&r);
         self.apply_transform(&trans);
     }
-    fn scale(&mut self, s: Float) {
+    /// Rotates by `angle` radians about `axis` (need not be
+    /// normalized), using the right-hand rule. Unlike `rotate`, which
+    /// packs axis and angle into one rotation vector (see
+    /// `Transform::rotate`), this takes them separately.
+    fn rotate_axis_angle(&mut self, axis: Vector<S>, angle: S) {
+        let trans = Transform::rotate_axis_angle(&axis, angle);
+        self.apply_transform(&trans);
+    }
+    /// Rotates by the given quaternion, as `[x, y, z, w]` (vector part
+    /// then scalar part); `q` need not be normalized.
+    fn rotate_quat(&mut self, q: [S; 4]) {
+        let trans = Transform::from_quaternion(q);
+        self.apply_transform(&trans);
+    }
+    fn scale(&mut self, s: S) {
         let trans = Transform::scale(s);
         self.apply_transform(&trans);
     }
@@ -39,34 +53,34 @@ pub trait Object: ImplicitFunction + ObjectClone {
     }
 }
 
-pub trait ObjectClone {
-    fn clone_box(&self) -> Box<Object>;
+pub trait ObjectClone<S: Scalar = Float> {
+    fn clone_box(&self) -> Box<Object<S>>;
 }
 
-impl<T> ObjectClone for T
-    where T: 'static + Object + Clone
+impl<S: Scalar, T> ObjectClone<S> for T
+    where T: 'static + Object<S> + Clone
 {
-    fn clone_box(&self) -> Box<Object> {
+    fn clone_box(&self) -> Box<Object<S>> {
         Box::new(self.clone())
     }
 }
 
 // We can now implement Clone manually by forwarding to clone_box.
-impl Clone for Box<Object> {
-    fn clone(&self) -> Box<Object> {
+impl<S: Scalar> Clone for Box<Object<S>> {
+    fn clone(&self) -> Box<Object<S>> {
         self.clone_box()
     }
 }
 
 // TODO: This is a hack. Replace it with something sane.
-impl PartialEq for Box<Object> {
+impl<S: Scalar> PartialEq for Box<Object<S>> {
     fn eq(&self, other: &Self) -> bool {
         self.to_string() == other.to_string()
     }
 }
 
 // TODO: This is a hack. Replace it with something sane.
-impl PartialOrd for Box<Object> {
+impl<S: Scalar> PartialOrd for Box<Object<S>> {
     fn partial_cmp(&self, other: &Self) -> Option<::std::cmp::Ordering> {
         let s = self.to_string();
         let o = other.to_string();
@@ -81,109 +95,368 @@ impl PartialOrd for Box<Object> {
 }
 
 #[derive(Clone, Debug, PartialEq)]
-pub struct PrimitiveWrapper<T: Primitive> {
+pub struct PrimitiveWrapper<T: Primitive<S>, S: Scalar = Float> {
     primitive: Box<T>,
-    transform: Transform,
+    transform: Transform<S>,
 }
 
-impl<T: Primitive + 'static> ImplicitFunction for PrimitiveWrapper<T> {
-    fn value(&self, p: &Point) -> Float {
+impl<T: Primitive<S> + 'static, S: Scalar> ImplicitFunction<S> for PrimitiveWrapper<T, S> {
+    fn value(&self, p: &Point<S>) -> S {
         self.primitive.value(&self.transform.t_point(*p))
     }
-    fn normal(&self, p: &Point) -> Vector {
+    fn normal(&self, p: &Point<S>) -> Vector<S> {
         self.transform
             .i_vector(self.primitive.normal(&self.transform.t_point(*p)))
             .normalize()
     }
 }
-impl<T: Primitive + 'static> Object for PrimitiveWrapper<T> {
-    fn apply_transform(&mut self, other: &Transform) {
+impl<T: Primitive<S> + 'static, S: Scalar> Object<S> for PrimitiveWrapper<T, S> {
+    fn apply_transform(&mut self, other: &Transform<S>) {
         self.transform = self.transform.concat(other)
     }
 }
 
 #[derive(Clone, Debug, PartialEq)]
-pub struct SpherePrimitive {
-    radius: Float,
+pub struct SpherePrimitive<S: Scalar = Float> {
+    radius: S,
 }
 
-impl SpherePrimitive {
-    pub fn new(r: Float) -> Box<SpherePrimitive> {
+impl<S: Scalar> SpherePrimitive<S> {
+    pub fn new(r: S) -> Box<SpherePrimitive<S>> {
         Box::new(SpherePrimitive { radius: r })
     }
 }
 
-impl ImplicitFunction for SpherePrimitive {
-    fn value(&self, p: &Point) -> Float {
+impl<S: Scalar> ImplicitFunction<S> for SpherePrimitive<S> {
+    fn value(&self, p: &Point<S>) -> S {
         return p.to_vec().length() - self.radius;
     }
-    fn normal(&self, p: &Point) -> Vector {
+    fn normal(&self, p: &Point<S>) -> Vector<S> {
         return p.to_vec().normalize();
     }
 }
 
-impl Primitive for SpherePrimitive {}
+impl<S: Scalar> Primitive<S> for SpherePrimitive<S> {}
 
-pub type Sphere = PrimitiveWrapper<SpherePrimitive>;
+pub type Sphere<S = Float> = PrimitiveWrapper<SpherePrimitive<S>, S>;
 
-impl Sphere {
-    pub fn new(r: Float) -> Box<Sphere> {
-        Box::new(Sphere {
+impl<S: Scalar> Sphere<S> {
+    pub fn new(r: S) -> Box<Sphere<S>> {
+        Box::new(PrimitiveWrapper {
             primitive: SpherePrimitive::new(r),
             transform: Transform::identity(),
         })
     }
 }
 
-pub trait Mixer: Clone + Debug {
-    fn new(Float) -> Box<Self>;
-    fn mixval(&self, a: Float, b: Float) -> Float;
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoxPrimitive<S: Scalar = Float> {
+    half_extent: Vector<S>,
+}
+
+impl<S: Scalar> BoxPrimitive<S> {
+    pub fn new(half_extent: Vector<S>) -> Box<BoxPrimitive<S>> {
+        Box::new(BoxPrimitive { half_extent: half_extent })
+    }
+}
+
+impl<S: Scalar> ImplicitFunction<S> for BoxPrimitive<S> {
+    fn value(&self, p: &Point<S>) -> S {
+        let zero = S::zero();
+        let qx = p.x.abs() - self.half_extent.x;
+        let qy = p.y.abs() - self.half_extent.y;
+        let qz = p.z.abs() - self.half_extent.z;
+        let outside = Vector::new(qx.max(zero), qy.max(zero), qz.max(zero)).length();
+        let inside = qx.max(qy.max(qz)).min(zero);
+        outside + inside
+    }
+    fn normal(&self, p: &Point<S>) -> Vector<S> {
+        let zero = S::zero();
+        let qx = p.x.abs() - self.half_extent.x;
+        let qy = p.y.abs() - self.half_extent.y;
+        let qz = p.z.abs() - self.half_extent.z;
+        // The face whose extent is exceeded most is the nearest one.
+        if qx > qy && qx > qz {
+            Vector::new(p.x.signum(), zero, zero)
+        } else if qy > qz {
+            Vector::new(zero, p.y.signum(), zero)
+        } else {
+            Vector::new(zero, zero, p.z.signum())
+        }
+    }
+}
+
+impl<S: Scalar> Primitive<S> for BoxPrimitive<S> {}
+
+// Named `Cuboid` rather than `Box` because `Box` is already
+// `std::boxed::Box`, which this module uses throughout.
+pub type Cuboid<S = Float> = PrimitiveWrapper<BoxPrimitive<S>, S>;
+
+impl<S: Scalar> Cuboid<S> {
+    pub fn new(half_extent: Vector<S>) -> Box<Cuboid<S>> {
+        Box::new(PrimitiveWrapper {
+            primitive: BoxPrimitive::new(half_extent),
+            transform: Transform::identity(),
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CylinderPrimitive<S: Scalar = Float> {
+    radius: S,
+    half_height: S,
+}
+
+impl<S: Scalar> CylinderPrimitive<S> {
+    /// A cylinder with its axis along `y`, spanning `y` in
+    /// `[-half_height, half_height]`.
+    pub fn new(radius: S, half_height: S) -> Box<CylinderPrimitive<S>> {
+        Box::new(CylinderPrimitive {
+            radius: radius,
+            half_height: half_height,
+        })
+    }
+}
+
+impl<S: Scalar> ImplicitFunction<S> for CylinderPrimitive<S> {
+    fn value(&self, p: &Point<S>) -> S {
+        let zero = S::zero();
+        let dx = (p.x * p.x + p.z * p.z).sqrt() - self.radius;
+        let dy = p.y.abs() - self.half_height;
+        let outside = Vector::new(dx.max(zero), dy.max(zero), zero).length();
+        dx.max(dy).min(zero) + outside
+    }
+    fn normal(&self, p: &Point<S>) -> Vector<S> {
+        let zero = S::zero();
+        let dx = (p.x * p.x + p.z * p.z).sqrt() - self.radius;
+        let dy = p.y.abs() - self.half_height;
+        if dy > dx {
+            Vector::new(zero, p.y.signum(), zero)
+        } else {
+            Vector::new(p.x, zero, p.z).normalize()
+        }
+    }
+}
+
+impl<S: Scalar> Primitive<S> for CylinderPrimitive<S> {}
+
+pub type Cylinder<S = Float> = PrimitiveWrapper<CylinderPrimitive<S>, S>;
+
+impl<S: Scalar> Cylinder<S> {
+    pub fn new(radius: S, half_height: S) -> Box<Cylinder<S>> {
+        Box::new(PrimitiveWrapper {
+            primitive: CylinderPrimitive::new(radius, half_height),
+            transform: Transform::identity(),
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TorusPrimitive<S: Scalar = Float> {
+    major_radius: S,
+    minor_radius: S,
+}
+
+impl<S: Scalar> TorusPrimitive<S> {
+    /// A torus lying in the `xz` plane, revolved around `y`:
+    /// `major_radius` is the distance from the center to the middle of
+    /// the tube, `minor_radius` is the tube's own radius.
+    pub fn new(major_radius: S, minor_radius: S) -> Box<TorusPrimitive<S>> {
+        Box::new(TorusPrimitive {
+            major_radius: major_radius,
+            minor_radius: minor_radius,
+        })
+    }
+}
+
+impl<S: Scalar> ImplicitFunction<S> for TorusPrimitive<S> {
+    fn value(&self, p: &Point<S>) -> S {
+        let radial = (p.x * p.x + p.z * p.z).sqrt() - self.major_radius;
+        (radial * radial + p.y * p.y).sqrt() - self.minor_radius
+    }
+    fn normal(&self, p: &Point<S>) -> Vector<S> {
+        // The gradient of the tube distance isn't as cheap to special
+        // case as the other primitives here, so fall back to the
+        // numerical estimate.
+        normal_from_implicit(self, p)
+    }
+}
+
+impl<S: Scalar> Primitive<S> for TorusPrimitive<S> {}
+
+pub type Torus<S = Float> = PrimitiveWrapper<TorusPrimitive<S>, S>;
+
+impl<S: Scalar> Torus<S> {
+    pub fn new(major_radius: S, minor_radius: S) -> Box<Torus<S>> {
+        Box::new(PrimitiveWrapper {
+            primitive: TorusPrimitive::new(major_radius, minor_radius),
+            transform: Transform::identity(),
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConePrimitive<S: Scalar = Float> {
+    half_height: S,
+    radius_bottom: S,
+    radius_top: S,
+}
+
+impl<S: Scalar> ConePrimitive<S> {
+    /// A (optionally frustum) cone with its axis along `y`, spanning
+    /// `y` in `[-half_height, half_height]`, with `radius_bottom` at
+    /// `y = -half_height` and `radius_top` at `y = half_height`;
+    /// `radius_top` of `0` gives a sharp apex.
+    pub fn new(half_height: S, radius_bottom: S, radius_top: S) -> Box<ConePrimitive<S>> {
+        Box::new(ConePrimitive {
+            half_height: half_height,
+            radius_bottom: radius_bottom,
+            radius_top: radius_top,
+        })
+    }
+}
+
+impl<S: Scalar> ImplicitFunction<S> for ConePrimitive<S> {
+    fn value(&self, p: &Point<S>) -> S {
+        // Exact capped-cone distance (Quilez): project to the (radial,
+        // height) half-plane and take the nearer of the cap segment
+        // `ca` and the side segment `cb`, signed by whether we ended up
+        // inside both.
+        let zero = S::zero();
+        let h = self.half_height;
+        let r1 = self.radius_bottom;
+        let r2 = self.radius_top;
+        let qx = (p.x * p.x + p.z * p.z).sqrt();
+        let qy = p.y;
+
+        let k1 = (r2, h);
+        let k2 = (r2 - r1, h + h);
+
+        let ca_x = qx - qx.min(if qy < zero { r1 } else { r2 });
+        let ca_y = qy.abs() - h;
+
+        let k_minus_q = (k1.0 - qx, k1.1 - qy);
+        let t = ((k_minus_q.0 * k2.0 + k_minus_q.1 * k2.1) / (k2.0 * k2.0 + k2.1 * k2.1))
+            .max(zero)
+            .min(S::one());
+        let cb_x = qx - k1.0 + k2.0 * t;
+        let cb_y = qy - k1.1 + k2.1 * t;
+
+        let sign = if cb_x < zero && ca_y < zero {
+            S::zero() - S::one()
+        } else {
+            S::one()
+        };
+        sign * (ca_x * ca_x + ca_y * ca_y).min(cb_x * cb_x + cb_y * cb_y).sqrt()
+    }
+    fn normal(&self, p: &Point<S>) -> Vector<S> {
+        normal_from_implicit(self, p)
+    }
+}
+
+impl<S: Scalar> Primitive<S> for ConePrimitive<S> {}
+
+pub type Cone<S = Float> = PrimitiveWrapper<ConePrimitive<S>, S>;
+
+impl<S: Scalar> Cone<S> {
+    pub fn new(half_height: S, radius_bottom: S, radius_top: S) -> Box<Cone<S>> {
+        Box::new(PrimitiveWrapper {
+            primitive: ConePrimitive::new(half_height, radius_bottom, radius_top),
+            transform: Transform::identity(),
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlanePrimitive<S: Scalar = Float> {
+    normal: Vector<S>,
+    offset: S,
+}
+
+impl<S: Scalar> PlanePrimitive<S> {
+    /// The solid half-space `dot(p, normal) <= offset`, i.e. the side
+    /// `normal` points *away* from, matching this crate's
+    /// negative-is-inside convention (see `SpherePrimitive`); `normal`
+    /// need not be normalized.
+    pub fn new(normal: Vector<S>, offset: S) -> Box<PlanePrimitive<S>> {
+        Box::new(PlanePrimitive {
+            normal: normal.normalize(),
+            offset: offset,
+        })
+    }
+}
+
+impl<S: Scalar> ImplicitFunction<S> for PlanePrimitive<S> {
+    fn value(&self, p: &Point<S>) -> S {
+        p.to_vec().dot(self.normal) - self.offset
+    }
+    fn normal(&self, _p: &Point<S>) -> Vector<S> {
+        self.normal
+    }
+}
+
+impl<S: Scalar> Primitive<S> for PlanePrimitive<S> {}
+
+pub type Plane<S = Float> = PrimitiveWrapper<PlanePrimitive<S>, S>;
+
+impl<S: Scalar> Plane<S> {
+    pub fn new(normal: Vector<S>, offset: S) -> Box<Plane<S>> {
+        Box::new(PrimitiveWrapper {
+            primitive: PlanePrimitive::new(normal, offset),
+            transform: Transform::identity(),
+        })
+    }
+}
+
+pub trait Mixer<S: Scalar = Float>: Clone + Debug {
+    fn new(r: S) -> Box<Self>;
+    fn mixval(&self, a: S, b: S) -> S;
     fn mixnormal(&self,
-                 a: Float,
-                 b: Float,
-                 get_an: &Fn() -> Vector,
-                 get_bn: &Fn() -> Vector)
-                 -> Vector;
-    fn r(&self) -> Float;
+                 a: S,
+                 b: S,
+                 get_an: &Fn() -> Vector<S>,
+                 get_bn: &Fn() -> Vector<S>)
+                 -> Vector<S>;
+    fn r(&self) -> S;
 }
 
 #[derive(Clone, Debug)]
-pub struct Bool<T: Mixer> {
-    a: Box<Object>,
-    b: Box<Object>,
+pub struct Bool<T: Mixer<S>, S: Scalar = Float> {
+    a: Box<Object<S>>,
+    b: Box<Object<S>>,
     mixer: Box<T>,
 }
 
-impl<T: Mixer + 'static> Bool<T> {
-    pub fn new(a: Box<Object>, b: Box<Object>, r: Float) -> Box<Bool<T>> {
-        Box::new(Bool::<T> {
+impl<T: Mixer<S> + 'static, S: Scalar> Bool<T, S> {
+    pub fn new(a: Box<Object<S>>, b: Box<Object<S>>, r: S) -> Box<Bool<T, S>> {
+        Box::new(Bool::<T, S> {
             a: a,
             b: b,
             mixer: T::new(r),
         })
     }
-    pub fn from_vec(mut v: Vec<Box<Object>>, r: Float) -> Option<Box<Object>> {
+    pub fn from_vec(mut v: Vec<Box<Object<S>>>, r: S) -> Option<Box<Object<S>>> {
         match v.len() {
             0 => None,
             1 => Some(v.pop().unwrap()),
             _ => {
                 let l2 = v.len() / 2;
                 let v2 = v.split_off(l2);
-                Some(Bool::<T>::new(Bool::<T>::from_vec(v, r).unwrap(),
-                                    Bool::<T>::from_vec(v2, r).unwrap(),
-                                    r))
+                Some(Bool::<T, S>::new(Bool::<T, S>::from_vec(v, r).unwrap(),
+                                       Bool::<T, S>::from_vec(v2, r).unwrap(),
+                                       r))
             }
         }
     }
 }
 
 
-impl<T: Mixer + 'static> ImplicitFunction for Bool<T> {
-    fn value(&self, p: &Point) -> Float {
+impl<T: Mixer<S> + 'static, S: Scalar> ImplicitFunction<S> for Bool<T, S> {
+    fn value(&self, p: &Point<S>) -> S {
         return self.mixer.mixval(self.a.value(p), self.b.value(p));
     }
 
-    fn normal(&self, p: &Point) -> Vector {
+    fn normal(&self, p: &Point<S>) -> Vector<S> {
         let va = self.a.value(p);
         let vb = self.b.value(p);
         if (va - vb).abs() < self.mixer.r() {
@@ -196,127 +469,285 @@ impl<T: Mixer + 'static> ImplicitFunction for Bool<T> {
         }
     }
 }
-impl<T: Mixer + 'static> Object for Bool<T> {
-    fn apply_transform(&mut self, other: &Transform) {
+impl<T: Mixer<S> + 'static, S: Scalar> Object<S> for Bool<T, S> {
+    fn apply_transform(&mut self, other: &Transform<S>) {
         self.a.apply_transform(other);
         self.b.apply_transform(other);
     }
 }
 
 #[derive(Clone, Debug)]
-pub struct UnionMixer {
-    r: Float,
+pub struct UnionMixer<S: Scalar = Float> {
+    r: S,
 }
 
-fn rmin(a: Float, b: Float, r: Float) -> Float {
+// Generic over `Scalar` so the same rounded-min/max blend can be
+// instantiated at `f32` or `f64`.
+fn rmin<S: Scalar>(a: S, b: S, r: S) -> S {
     if (a - b).abs() < r {
-        return b + r * (f64::consts::PI / 4. + ((a - b) / r / 2_f64.sqrt()).asin()).sin() - r;
+        let quarter_pi = S::pi() / S::from_f64(4.0);
+        let sqrt2 = S::from_f64(2.0).sqrt();
+        return b + r * (quarter_pi + ((a - b) / r / sqrt2).asin()).sin() - r;
     }
     a.min(b)
 }
 
-fn rmax(a: Float, b: Float, r: Float) -> Float {
+fn rmax<S: Scalar>(a: S, b: S, r: S) -> S {
     if (a - b).abs() < r {
-        return b - r * (f64::consts::PI / 4. - ((a - b) / r / 2_f64.sqrt()).asin()).sin() + r;
+        let quarter_pi = S::pi() / S::from_f64(4.0);
+        let sqrt2 = S::from_f64(2.0).sqrt();
+        return b - r * (quarter_pi - ((a - b) / r / sqrt2).asin()).sin() + r;
     }
     a.max(b)
 }
 
-impl Mixer for UnionMixer {
-    fn new(r: Float) -> Box<Self> {
+impl<S: Scalar> Mixer<S> for UnionMixer<S> {
+    fn new(r: S) -> Box<Self> {
         Box::new(UnionMixer { r: r })
     }
-    fn mixval(&self, a: Float, b: Float) -> Float {
+    fn mixval(&self, a: S, b: S) -> S {
         rmin(a, b, self.r)
     }
     fn mixnormal(&self,
-                 a: Float,
-                 b: Float,
-                 get_an: &Fn() -> Vector,
-                 get_bn: &Fn() -> Vector)
-                 -> Vector {
+                 a: S,
+                 b: S,
+                 get_an: &Fn() -> Vector<S>,
+                 get_bn: &Fn() -> Vector<S>)
+                 -> Vector<S> {
         if a < b {
             get_an()
         } else {
             get_bn()
         }
     }
-    fn r(&self) -> Float {
+    fn r(&self) -> S {
         self.r
     }
 }
 
-pub type Union = Bool<UnionMixer>;
+pub type Union<S = Float> = Bool<UnionMixer<S>, S>;
 
 #[derive(Clone, Debug)]
-pub struct IntersectionMixer {
-    r: Float,
+pub struct IntersectionMixer<S: Scalar = Float> {
+    r: S,
 }
-impl Mixer for IntersectionMixer {
-    fn new(r: Float) -> Box<Self> {
+impl<S: Scalar> Mixer<S> for IntersectionMixer<S> {
+    fn new(r: S) -> Box<Self> {
         Box::new(IntersectionMixer { r: r })
     }
-    fn mixval(&self, a: Float, b: Float) -> Float {
+    fn mixval(&self, a: S, b: S) -> S {
         rmax(a, b, self.r)
     }
     fn mixnormal(&self,
-                 a: Float,
-                 b: Float,
-                 get_an: &Fn() -> Vector,
-                 get_bn: &Fn() -> Vector)
-                 -> Vector {
+                 a: S,
+                 b: S,
+                 get_an: &Fn() -> Vector<S>,
+                 get_bn: &Fn() -> Vector<S>)
+                 -> Vector<S> {
         if a > b {
             get_an()
         } else {
             get_bn()
         }
     }
-    fn r(&self) -> Float {
+    fn r(&self) -> S {
         self.r
     }
 }
 
-pub type Intersection = Bool<IntersectionMixer>;
+pub type Intersection<S = Float> = Bool<IntersectionMixer<S>, S>;
 
 #[derive(Clone, Debug)]
-pub struct SubtractionMixer {
-    r: Float,
+pub struct SubtractionMixer<S: Scalar = Float> {
+    r: S,
 }
-impl Mixer for SubtractionMixer {
-    fn new(r: Float) -> Box<Self> {
+impl<S: Scalar> Mixer<S> for SubtractionMixer<S> {
+    fn new(r: S) -> Box<Self> {
         Box::new(SubtractionMixer { r: r })
     }
-    fn mixval(&self, a: Float, b: Float) -> Float {
-        rmax(a, -b, self.r)
+    fn mixval(&self, a: S, b: S) -> S {
+        rmax(a, S::zero() - b, self.r)
     }
     fn mixnormal(&self,
-                 a: Float,
-                 b: Float,
-                 get_an: &Fn() -> Vector,
-                 get_bn: &Fn() -> Vector)
-                 -> Vector {
-        if a > -b {
+                 a: S,
+                 b: S,
+                 get_an: &Fn() -> Vector<S>,
+                 get_bn: &Fn() -> Vector<S>)
+                 -> Vector<S> {
+        if a > S::zero() - b {
             get_an()
         } else {
-            get_bn() * -1.
+            get_bn() * (S::zero() - S::one())
         }
     }
-    fn r(&self) -> Float {
+    fn r(&self) -> S {
         self.r
     }
 }
 
-pub type Subtraction = Bool<SubtractionMixer>;
+pub type Subtraction<S = Float> = Bool<SubtractionMixer<S>, S>;
 
-impl Bool<SubtractionMixer> {
-    pub fn subtraction_from_vec(mut v: Vec<Box<Object>>, r: Float) -> Option<Box<Object>> {
+impl<S: Scalar> Bool<SubtractionMixer<S>, S> {
+    pub fn subtraction_from_vec(mut v: Vec<Box<Object<S>>>, r: S) -> Option<Box<Object<S>>> {
         match v.len() {
             0 => None,
             1 => Some(v.pop().unwrap()),
             _ => {
                 let v_rest = v.split_off(1);
-                Some(Subtraction::new(v.pop().unwrap(), Union::from_vec(v_rest, r).unwrap(), r))
+                Some(Subtraction::new(v.pop().unwrap(),
+                                      Union::from_vec(v_rest, r).unwrap(),
+                                      r))
             }
         }
     }
 }
+
+// Below `morph_blend_epsilon()` of either end, `t` is close enough to 0 or
+// 1 that blending the two children's own normals is a good approximation
+// of the true gradient, avoiding a numerical-gradient evaluation for the
+// common case of a morph that is mostly one shape or the other.
+fn morph_blend_epsilon<S: Scalar>() -> S {
+    S::from_f64(1e-6)
+}
+
+/// Interpolates between two solids: `value` is the linear blend
+/// `(1 - t) * a.value + t * b.value`, giving continuous shape blends or
+/// animation keyframes between arbitrary CSG trees without rebuilding
+/// them.
+#[derive(Clone, Debug)]
+pub struct Morph<S: Scalar = Float> {
+    a: Box<Object<S>>,
+    b: Box<Object<S>>,
+    t: S,
+}
+
+impl<S: Scalar> Morph<S> {
+    /// `t` is clamped into `[0, 1]`, where `0` yields `a` and `1`
+    /// yields `b`.
+    pub fn new(a: Box<Object<S>>, b: Box<Object<S>>, t: S) -> Box<Morph<S>> {
+        Box::new(Morph {
+            a: a,
+            b: b,
+            t: t.max(S::zero()).min(S::one()),
+        })
+    }
+}
+
+impl<S: Scalar> ImplicitFunction<S> for Morph<S> {
+    fn value(&self, p: &Point<S>) -> S {
+        (S::one() - self.t) * self.a.value(p) + self.t * self.b.value(p)
+    }
+
+    fn normal(&self, p: &Point<S>) -> Vector<S> {
+        let epsilon = morph_blend_epsilon();
+        if self.t < epsilon || self.t > S::one() - epsilon {
+            (self.a.normal(p) * (S::one() - self.t) + self.b.normal(p) * self.t).normalize()
+        } else {
+            // A linear mix of two normals is not generally the true
+            // gradient direction away from the ends, so fall back to
+            // the numerical gradient, mirroring how `Bool::normal`
+            // switches near its crossover.
+            normal_from_implicit(self, p)
+        }
+    }
+}
+
+impl<S: Scalar> Object<S> for Morph<S> {
+    fn apply_transform(&mut self, other: &Transform<S>) {
+        self.a.apply_transform(other);
+        self.b.apply_transform(other);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::Point;
+
+    #[test]
+    fn sphere_value_is_distance_to_surface() {
+        let sphere = Sphere::new(1.0);
+        assert!((sphere.value(&Point::new(2.0, 0.0, 0.0)) - 1.0).abs() < 1e-9);
+        assert!((sphere.value(&Point::new(0.0, 0.0, 0.0)) - (-1.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn cuboid_value_outside_a_corner_matches_exact_distance() {
+        let cuboid = Cuboid::new(Vector::new(1.0, 1.0, 1.0));
+        // Well outside every face, so the exact distance is the length
+        // from the point to the nearest corner.
+        let p = Point::new(2.0, 2.0, 2.0);
+        let expected = 3.0_f64.sqrt();
+        assert!((cuboid.value(&p) - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn plane_solid_side_matches_negative_is_inside_convention() {
+        // normal (1, 0, 0), offset 1: the solid is x <= 1, so a point
+        // at x = 0 is inside (negative) and a point at x = 2 is
+        // outside (positive), the same sign convention SpherePrimitive
+        // uses.
+        let plane = Plane::new(Vector::new(1.0, 0.0, 0.0), 1.0);
+        assert!(plane.value(&Point::new(0.0, 0.0, 0.0)) < 0.0);
+        assert!(plane.value(&Point::new(2.0, 0.0, 0.0)) > 0.0);
+    }
+
+    #[test]
+    fn morph_at_the_ends_matches_its_two_children_exactly() {
+        let p = Point::new(0.3, 0.2, 0.1);
+        let a = Sphere::new(1.0);
+        let b = Cuboid::new(Vector::new(1.0, 1.0, 1.0));
+
+        let at_a = Morph::new(Sphere::new(1.0), Cuboid::new(Vector::new(1.0, 1.0, 1.0)), 0.0);
+        assert!((at_a.value(&p) - a.value(&p)).abs() < 1e-9);
+
+        let at_b = Morph::new(Sphere::new(1.0), Cuboid::new(Vector::new(1.0, 1.0, 1.0)), 1.0);
+        assert!((at_b.value(&p) - b.value(&p)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn morph_clamps_t_outside_zero_one() {
+        let p = Point::new(0.3, 0.2, 0.1);
+        let a = Sphere::new(1.0);
+        let b = Cuboid::new(Vector::new(1.0, 1.0, 1.0));
+
+        let below = Morph::new(Sphere::new(1.0), Cuboid::new(Vector::new(1.0, 1.0, 1.0)), -5.0);
+        assert!((below.value(&p) - a.value(&p)).abs() < 1e-9);
+
+        let above = Morph::new(Sphere::new(1.0), Cuboid::new(Vector::new(1.0, 1.0, 1.0)), 5.0);
+        assert!((above.value(&p) - b.value(&p)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn object_rotate_axis_angle_by_90_degrees_about_z_swaps_x_and_y_extents() {
+        // A box that's long along x (half-extent 2) and short along y
+        // (half-extent 1): a point just inside the x face should end up
+        // outside after a 90-degree rotation about z swaps the two axes,
+        // and vice versa.
+        let on_x_axis = Point::new(1.5, 0.0, 0.0);
+        let on_y_axis = Point::new(0.0, 1.5, 0.0);
+
+        let mut cuboid = Cuboid::new(Vector::new(2.0, 1.0, 1.0));
+        assert!(cuboid.value(&on_x_axis) < 0.0);
+        assert!(cuboid.value(&on_y_axis) > 0.0);
+
+        cuboid.rotate_axis_angle(Vector::new(0.0, 0.0, 1.0), ::std::f64::consts::FRAC_PI_2);
+        assert!(cuboid.value(&on_x_axis) > 0.0);
+        assert!(cuboid.value(&on_y_axis) < 0.0);
+    }
+
+    #[test]
+    fn object_rotate_quat_by_90_degrees_about_z_swaps_x_and_y_extents() {
+        let half = ::std::f64::consts::FRAC_PI_4;
+        let on_x_axis = Point::new(1.5, 0.0, 0.0);
+        let on_y_axis = Point::new(0.0, 1.5, 0.0);
+
+        let mut cuboid = Cuboid::new(Vector::new(2.0, 1.0, 1.0));
+        assert!(cuboid.value(&on_x_axis) < 0.0);
+        assert!(cuboid.value(&on_y_axis) > 0.0);
+
+        cuboid.rotate_quat([0.0, 0.0, half.sin(), half.cos()]);
+        assert!(cuboid.value(&on_x_axis) > 0.0);
+        assert!(cuboid.value(&on_y_axis) < 0.0);
+    }
+}
@@ -0,0 +1,124 @@
+use std::f32;
+use std::f64;
+use std::fmt::Debug;
+use std::ops::{Add, Sub, Mul, Div};
+
+/// A floating-point scalar usable throughout the geometry kernel —
+/// `ImplicitFunction`, `Point`/`Vector`/`Transform`, the primitives and
+/// `Mixer`s — so that the same `Sphere`/`Union` code can be instantiated
+/// at either `f32` (fast preview meshing) or `f64` (high-accuracy
+/// export) instead of being hard-wired to one precision.
+///
+/// `Float` remains the crate's default (`= f64`), and every generic
+/// type here defaults its scalar parameter to it, so existing source
+/// that never names `f32` keeps compiling unchanged.
+pub trait Scalar
+    : 'static + Copy + Debug + PartialOrd + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Div<Output = Self>
+    {
+    fn sqrt(self) -> Self;
+    fn asin(self) -> Self;
+    fn atan2(self, other: Self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn sin_cos(self) -> (Self, Self) {
+        (self.sin(), self.cos())
+    }
+    fn min(self, other: Self) -> Self;
+    fn max(self, other: Self) -> Self;
+    fn abs(self) -> Self;
+    fn signum(self) -> Self;
+    fn pi() -> Self;
+    fn epsilon() -> Self;
+    fn from_f64(v: f64) -> Self;
+    fn to_f64(self) -> f64;
+    fn zero() -> Self {
+        Self::from_f64(0.0)
+    }
+    fn one() -> Self {
+        Self::from_f64(1.0)
+    }
+}
+
+impl Scalar for f32 {
+    fn sqrt(self) -> Self {
+        f32::sqrt(self)
+    }
+    fn asin(self) -> Self {
+        f32::asin(self)
+    }
+    fn atan2(self, other: Self) -> Self {
+        f32::atan2(self, other)
+    }
+    fn sin(self) -> Self {
+        f32::sin(self)
+    }
+    fn cos(self) -> Self {
+        f32::cos(self)
+    }
+    fn min(self, other: Self) -> Self {
+        f32::min(self, other)
+    }
+    fn max(self, other: Self) -> Self {
+        f32::max(self, other)
+    }
+    fn abs(self) -> Self {
+        f32::abs(self)
+    }
+    fn signum(self) -> Self {
+        f32::signum(self)
+    }
+    fn pi() -> Self {
+        f32::consts::PI
+    }
+    fn epsilon() -> Self {
+        f32::EPSILON
+    }
+    fn from_f64(v: f64) -> Self {
+        v as f32
+    }
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+}
+
+impl Scalar for f64 {
+    fn sqrt(self) -> Self {
+        f64::sqrt(self)
+    }
+    fn asin(self) -> Self {
+        f64::asin(self)
+    }
+    fn atan2(self, other: Self) -> Self {
+        f64::atan2(self, other)
+    }
+    fn sin(self) -> Self {
+        f64::sin(self)
+    }
+    fn cos(self) -> Self {
+        f64::cos(self)
+    }
+    fn min(self, other: Self) -> Self {
+        f64::min(self, other)
+    }
+    fn max(self, other: Self) -> Self {
+        f64::max(self, other)
+    }
+    fn abs(self) -> Self {
+        f64::abs(self)
+    }
+    fn signum(self) -> Self {
+        f64::signum(self)
+    }
+    fn pi() -> Self {
+        f64::consts::PI
+    }
+    fn epsilon() -> Self {
+        f64::EPSILON
+    }
+    fn from_f64(v: f64) -> Self {
+        v
+    }
+    fn to_f64(self) -> f64 {
+        self
+    }
+}
@@ -0,0 +1,306 @@
+use std::ops::{Add, Sub, Mul};
+
+use Float;
+use scalar::Scalar;
+
+/// A point in 3D space, generic over its scalar type (see `Scalar`) so
+/// the same modeling code can run at `f32` or `f64`; `Float` (`= f64`)
+/// is the default, matching every other type in this module.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Point<S: Scalar = Float> {
+    pub x: S,
+    pub y: S,
+    pub z: S,
+}
+
+impl<S: Scalar> Point<S> {
+    pub fn new(x: S, y: S, z: S) -> Point<S> {
+        Point { x: x, y: y, z: z }
+    }
+    pub fn from_vec(v: Vector<S>) -> Point<S> {
+        Point::new(v.x, v.y, v.z)
+    }
+    pub fn to_vec(&self) -> Vector<S> {
+        Vector::new(self.x, self.y, self.z)
+    }
+}
+
+impl<S: Scalar> Add<Vector<S>> for Point<S> {
+    type Output = Point<S>;
+    fn add(self, v: Vector<S>) -> Point<S> {
+        Point::new(self.x + v.x, self.y + v.y, self.z + v.z)
+    }
+}
+
+impl<S: Scalar> Sub<Vector<S>> for Point<S> {
+    type Output = Point<S>;
+    fn sub(self, v: Vector<S>) -> Point<S> {
+        Point::new(self.x - v.x, self.y - v.y, self.z - v.z)
+    }
+}
+
+impl<S: Scalar> Sub<Point<S>> for Point<S> {
+    type Output = Vector<S>;
+    fn sub(self, other: Point<S>) -> Vector<S> {
+        Vector::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+/// A displacement/direction in 3D space, generic over its scalar type
+/// like `Point`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Vector<S: Scalar = Float> {
+    pub x: S,
+    pub y: S,
+    pub z: S,
+}
+
+impl<S: Scalar> Vector<S> {
+    pub fn new(x: S, y: S, z: S) -> Vector<S> {
+        Vector { x: x, y: y, z: z }
+    }
+    pub fn dot(&self, other: Vector<S>) -> S {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+    pub fn cross(&self, other: Vector<S>) -> Vector<S> {
+        Vector::new(self.y * other.z - self.z * other.y,
+                    self.z * other.x - self.x * other.z,
+                    self.x * other.y - self.y * other.x)
+    }
+    pub fn length(&self) -> S {
+        self.dot(*self).sqrt()
+    }
+    pub fn normalize(&self) -> Vector<S> {
+        let len = self.length();
+        Vector::new(self.x / len, self.y / len, self.z / len)
+    }
+}
+
+impl<S: Scalar> Add for Vector<S> {
+    type Output = Vector<S>;
+    fn add(self, other: Vector<S>) -> Vector<S> {
+        Vector::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl<S: Scalar> Sub for Vector<S> {
+    type Output = Vector<S>;
+    fn sub(self, other: Vector<S>) -> Vector<S> {
+        Vector::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl<S: Scalar> Mul<S> for Vector<S> {
+    type Output = Vector<S>;
+    fn mul(self, s: S) -> Vector<S> {
+        Vector::new(self.x * s, self.y * s, self.z * s)
+    }
+}
+
+/// A small step along `x`/`y`/`z`, used by `normal_from_implicit` to
+/// estimate a gradient by forward finite differences. These are
+/// functions rather than `const`s since they're generic over `S`.
+pub fn epsilon_x<S: Scalar>() -> Vector<S> {
+    Vector::new(S::from_f64(1e-4), S::zero(), S::zero())
+}
+pub fn epsilon_y<S: Scalar>() -> Vector<S> {
+    Vector::new(S::zero(), S::from_f64(1e-4), S::zero())
+}
+pub fn epsilon_z<S: Scalar>() -> Vector<S> {
+    Vector::new(S::zero(), S::zero(), S::from_f64(1e-4))
+}
+
+/// A unit rotation quaternion `x*i + y*j + z*k + w`, used internally by
+/// `Transform` to represent and compose rotations (including the
+/// `Object::rotate_quat`/`rotate_axis_angle` constructors).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Quaternion<S: Scalar = Float> {
+    pub x: S,
+    pub y: S,
+    pub z: S,
+    pub w: S,
+}
+
+impl<S: Scalar> Quaternion<S> {
+    pub fn new(x: S, y: S, z: S, w: S) -> Quaternion<S> {
+        Quaternion { x: x, y: y, z: z, w: w }
+    }
+    pub fn identity() -> Quaternion<S> {
+        Quaternion::new(S::zero(), S::zero(), S::zero(), S::one())
+    }
+    pub fn from_axis_angle(axis: Vector<S>, angle: S) -> Quaternion<S> {
+        let axis = axis.normalize();
+        let half = angle / S::from_f64(2.0);
+        let (s, c) = half.sin_cos();
+        Quaternion::new(axis.x * s, axis.y * s, axis.z * s, c)
+    }
+    pub fn normalize(&self) -> Quaternion<S> {
+        let len = (self.x * self.x + self.y * self.y + self.z * self.z + self.w * self.w).sqrt();
+        Quaternion::new(self.x / len, self.y / len, self.z / len, self.w / len)
+    }
+    pub fn conjugate(&self) -> Quaternion<S> {
+        let zero = S::zero();
+        Quaternion::new(zero - self.x, zero - self.y, zero - self.z, self.w)
+    }
+    /// Hamilton product `self * other`: the rotation that applies
+    /// `other` first, then `self`.
+    pub fn multiply(&self, other: &Quaternion<S>) -> Quaternion<S> {
+        let (w1, x1, y1, z1) = (self.w, self.x, self.y, self.z);
+        let (w2, x2, y2, z2) = (other.w, other.x, other.y, other.z);
+        Quaternion::new(w1 * x2 + x1 * w2 + y1 * z2 - z1 * y2,
+                         w1 * y2 - x1 * z2 + y1 * w2 + z1 * x2,
+                         w1 * z2 + x1 * y2 - y1 * x2 + z1 * w2,
+                         w1 * w2 - x1 * x2 - y1 * y2 - z1 * z2)
+    }
+    /// Rotates `v` by this quaternion.
+    pub fn rotate_vector(&self, v: Vector<S>) -> Vector<S> {
+        let qv = Vector::new(self.x, self.y, self.z);
+        let t = qv.cross(v) * S::from_f64(2.0);
+        v + t * self.w + qv.cross(t)
+    }
+}
+
+/// An affine transform composed of a uniform `scale`, a rotation `rot`
+/// and a displacement `disp`, applied in that order (scale, then
+/// rotate, then translate) to map a local-space point into world
+/// space.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transform<S: Scalar = Float> {
+    pub scale: S,
+    pub rot: Quaternion<S>,
+    pub disp: Vector<S>,
+}
+
+impl<S: Scalar> Transform<S> {
+    pub fn identity() -> Transform<S> {
+        Transform {
+            scale: S::one(),
+            rot: Quaternion::identity(),
+            disp: Vector::new(S::zero(), S::zero(), S::zero()),
+        }
+    }
+    pub fn translate(t: &Vector<S>) -> Transform<S> {
+        Transform { scale: S::one(), rot: Quaternion::identity(), disp: *t }
+    }
+    pub fn scale(s: S) -> Transform<S> {
+        Transform {
+            scale: s,
+            rot: Quaternion::identity(),
+            disp: Vector::new(S::zero(), S::zero(), S::zero()),
+        }
+    }
+    /// Rotates by `angle` radians about `axis` (need not be
+    /// normalized), using the right-hand rule.
+    ///
+    /// # Panics
+    /// Panics if `axis` is the zero vector, which has no well-defined
+    /// rotation axis to normalize.
+    pub fn rotate_axis_angle(axis: &Vector<S>, angle: S) -> Transform<S> {
+        assert!(axis.length() > S::epsilon(),
+                "Transform::rotate_axis_angle: axis must not be the zero vector");
+        Transform {
+            scale: S::one(),
+            rot: Quaternion::from_axis_angle(*axis, angle),
+            disp: Vector::new(S::zero(), S::zero(), S::zero()),
+        }
+    }
+    /// Rotates by the quaternion `[x, y, z, w]` (vector part then
+    /// scalar part); `q` is normalized internally so the caller
+    /// doesn't have to.
+    ///
+    /// # Panics
+    /// Panics if `q` is the zero quaternion, which has no orientation
+    /// to normalize to.
+    pub fn from_quaternion(q: [S; 4]) -> Transform<S> {
+        let magnitude_sq = q[0] * q[0] + q[1] * q[1] + q[2] * q[2] + q[3] * q[3];
+        assert!(magnitude_sq > S::epsilon(),
+                "Transform::from_quaternion: q must not be the zero quaternion");
+        Transform {
+            scale: S::one(),
+            rot: Quaternion::new(q[0], q[1], q[2], q[3]).normalize(),
+            disp: Vector::new(S::zero(), S::zero(), S::zero()),
+        }
+    }
+    /// `r`'s direction is the rotation axis and its length (radians)
+    /// is the rotation angle; the zero vector yields the identity.
+    /// This "rotation vector" convention is easy to get turned around;
+    /// see `Object::rotate_axis_angle`/`rotate_quat` for an
+    /// unambiguous alternative.
+    pub fn rotate(r: &Vector<S>) -> Transform<S> {
+        let angle = r.length();
+        if angle < S::epsilon() {
+            Transform::identity()
+        } else {
+            Transform::rotate_axis_angle(r, angle)
+        }
+    }
+
+    /// Composes `self` followed by `other`: applying the result to a
+    /// point or vector is equivalent to applying `self`, then `other`.
+    pub fn concat(&self, other: &Transform<S>) -> Transform<S> {
+        Transform {
+            scale: self.scale * other.scale,
+            rot: other.rot.multiply(&self.rot),
+            disp: other.forward_vector(self.disp) + other.disp,
+        }
+    }
+
+    fn forward_vector(&self, v: Vector<S>) -> Vector<S> {
+        self.rot.rotate_vector(v) * self.scale
+    }
+
+    /// Maps a point from world space into this transform's local
+    /// space, e.g. so a `PrimitiveWrapper` can evaluate its primitive's
+    /// implicit function in the primitive's own untransformed frame.
+    pub fn t_point(&self, p: Point<S>) -> Point<S> {
+        let local = self.rot.conjugate().rotate_vector(p.to_vec() - self.disp) *
+                    (S::one() / self.scale);
+        Point::from_vec(local)
+    }
+
+    /// Maps a local-space normal direction into world space.
+    pub fn i_vector(&self, v: Vector<S>) -> Vector<S> {
+        self.rot.rotate_vector(v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_vector_close(v: Vector, expected: Vector) {
+        assert!((v.x - expected.x).abs() < 1e-9 && (v.y - expected.y).abs() < 1e-9 &&
+                (v.z - expected.z).abs() < 1e-9,
+                "{:?} != {:?}",
+                v,
+                expected);
+    }
+
+    #[test]
+    fn rotate_axis_angle_by_90_degrees_about_z_maps_x_onto_y() {
+        let t = Transform::rotate_axis_angle(&Vector::new(0.0, 0.0, 1.0),
+                                              ::std::f64::consts::FRAC_PI_2);
+        assert_vector_close(t.i_vector(Vector::new(1.0, 0.0, 0.0)), Vector::new(0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn rotate_axis_angle_panics_on_zero_axis() {
+        Transform::rotate_axis_angle(&Vector::new(0.0, 0.0, 0.0), 1.0);
+    }
+
+    #[test]
+    fn from_quaternion_by_90_degrees_about_z_maps_x_onto_y() {
+        // A quaternion encoding a 90-degree rotation about z: [x, y, z, w]
+        // = [0, 0, sin(45deg), cos(45deg)].
+        let half = ::std::f64::consts::FRAC_PI_4;
+        let t = Transform::from_quaternion([0.0, 0.0, half.sin(), half.cos()]);
+        assert_vector_close(t.i_vector(Vector::new(1.0, 0.0, 0.0)), Vector::new(0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_quaternion_panics_on_zero_quaternion() {
+        Transform::from_quaternion([0.0, 0.0, 0.0, 0.0]);
+    }
+}
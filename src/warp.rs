@@ -0,0 +1,218 @@
+use Float;
+
+use scalar::Scalar;
+use types::{Point, Vector, Transform};
+use primitive::{Object, ImplicitFunction, normal_from_implicit};
+
+/// Rotates `v` by `angle` radians about `axis` (assumed already
+/// normalized) using Rodrigues' rotation formula.
+fn rotate_vector<S: Scalar>(v: Vector<S>, axis: Vector<S>, angle: S) -> Vector<S> {
+    let (s, c) = angle.sin_cos();
+    v * c + axis.cross(v) * s + axis * (axis.dot(v) * (S::one() - c))
+}
+
+fn rotate_point<S: Scalar>(p: &Point<S>, axis: Vector<S>, angle: S) -> Point<S> {
+    let v = rotate_vector(p.to_vec(), axis, angle);
+    Point::new(v.x, v.y, v.z)
+}
+
+/// A screw motion: rotates the query point about `axis` by an angle
+/// proportional to its coordinate along that axis before delegating to
+/// `object`, i.e. `object` is progressively twisted as it extends along
+/// `axis`.
+///
+/// Like `Taper` and `Bend`, this warp is not distance-preserving, so the
+/// `value` it returns is only a bounded field, not an exact signed
+/// distance: that matters wherever the magnitude of `value` is used
+/// directly, such as the blending radius `r` in `Bool<T>`.
+#[derive(Clone, Debug)]
+pub struct Twist<S: Scalar = Float> {
+    object: Box<Object<S>>,
+    transform: Transform<S>,
+    axis: Vector<S>,
+    rate: S,
+}
+
+impl<S: Scalar> Twist<S> {
+    /// `rate` is the screw angle in radians applied per unit length
+    /// along `axis`; `axis` need not be normalized.
+    pub fn new(object: Box<Object<S>>, axis: Vector<S>, rate: S) -> Box<Twist<S>> {
+        Box::new(Twist {
+            object: object,
+            transform: Transform::identity(),
+            axis: axis.normalize(),
+            rate: rate,
+        })
+    }
+
+    fn untwist(&self, p: &Point<S>) -> Point<S> {
+        let p = self.transform.t_point(*p);
+        let height = p.to_vec().dot(self.axis);
+        rotate_point(&p, self.axis, S::zero() - height * self.rate)
+    }
+}
+
+impl<S: Scalar> ImplicitFunction<S> for Twist<S> {
+    fn value(&self, p: &Point<S>) -> S {
+        self.object.value(&self.untwist(p))
+    }
+    fn normal(&self, p: &Point<S>) -> Vector<S> {
+        normal_from_implicit(self, p)
+    }
+}
+
+impl<S: Scalar> Object<S> for Twist<S> {
+    fn apply_transform(&mut self, other: &Transform<S>) {
+        self.transform = self.transform.concat(other)
+    }
+}
+
+/// Scales the coordinates perpendicular to `axis` by a factor that
+/// varies linearly with the coordinate along `axis`, narrowing or
+/// widening `object` as it extends along that axis.
+///
+/// See `Twist` for why `value` here is a bounded field rather than an
+/// exact signed distance.
+#[derive(Clone, Debug)]
+pub struct Taper<S: Scalar = Float> {
+    object: Box<Object<S>>,
+    transform: Transform<S>,
+    axis: Vector<S>,
+    rate: S,
+}
+
+impl<S: Scalar> Taper<S> {
+    /// The perpendicular scale factor at height `h` along `axis` is
+    /// `1.0 + rate * h`; `rate` of `0` leaves `object` unchanged.
+    pub fn new(object: Box<Object<S>>, axis: Vector<S>, rate: S) -> Box<Taper<S>> {
+        Box::new(Taper {
+            object: object,
+            transform: Transform::identity(),
+            axis: axis.normalize(),
+            rate: rate,
+        })
+    }
+
+    fn untaper(&self, p: &Point<S>) -> Point<S> {
+        let p = self.transform.t_point(*p);
+        let v = p.to_vec();
+        let height = v.dot(self.axis);
+        let parallel = self.axis * height;
+        let perpendicular = v - parallel;
+        // Guard against the taper collapsing to (or through) zero scale,
+        // which would otherwise fold the field back on itself.
+        let scale = (S::one() + self.rate * height).max(S::from_f64(1e-6));
+        let untapered = parallel + perpendicular * (S::one() / scale);
+        Point::new(untapered.x, untapered.y, untapered.z)
+    }
+}
+
+impl<S: Scalar> ImplicitFunction<S> for Taper<S> {
+    fn value(&self, p: &Point<S>) -> S {
+        self.object.value(&self.untaper(p))
+    }
+    fn normal(&self, p: &Point<S>) -> Vector<S> {
+        normal_from_implicit(self, p)
+    }
+}
+
+impl<S: Scalar> Object<S> for Taper<S> {
+    fn apply_transform(&mut self, other: &Transform<S>) {
+        self.transform = self.transform.concat(other)
+    }
+}
+
+/// Bends a straight region of `object` in the `xy` plane into an arc of
+/// the given `radius` about an axis parallel to `z` through
+/// `(0, radius, 0)`, the way a straight bar can be curved into a ring:
+/// the object's local `x` axis becomes arc length and its local `y`
+/// axis becomes the offset from the arc toward its center (`radius`
+/// minus the distance from the center).
+///
+/// See `Twist` for why `value` here is a bounded field rather than an
+/// exact signed distance.
+#[derive(Clone, Debug)]
+pub struct Bend<S: Scalar = Float> {
+    object: Box<Object<S>>,
+    transform: Transform<S>,
+    radius: S,
+}
+
+impl<S: Scalar> Bend<S> {
+    pub fn new(object: Box<Object<S>>, radius: S) -> Box<Bend<S>> {
+        assert!(radius > S::zero());
+        Box::new(Bend {
+            object: object,
+            transform: Transform::identity(),
+            radius: radius,
+        })
+    }
+
+    fn unbend(&self, p: &Point<S>) -> Point<S> {
+        let p = self.transform.t_point(*p);
+        let center_to_p = self.radius - p.y;
+        let r = (p.x * p.x + center_to_p * center_to_p).sqrt();
+        let angle = p.x.atan2(center_to_p);
+        Point::new(angle * self.radius, self.radius - r, p.z)
+    }
+}
+
+impl<S: Scalar> ImplicitFunction<S> for Bend<S> {
+    fn value(&self, p: &Point<S>) -> S {
+        self.object.value(&self.unbend(p))
+    }
+    fn normal(&self, p: &Point<S>) -> Vector<S> {
+        normal_from_implicit(self, p)
+    }
+}
+
+impl<S: Scalar> Object<S> for Bend<S> {
+    fn apply_transform(&mut self, other: &Transform<S>) {
+        self.transform = self.transform.concat(other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitive::{Sphere, Plane};
+
+    #[test]
+    fn twist_with_zero_rate_matches_the_untransformed_object() {
+        let twisted = Twist::new(Sphere::new(1.0), Vector::new(0.0, 1.0, 0.0), 0.0);
+        let sphere = Sphere::new(1.0);
+        let p = Point::new(0.7, 0.3, 0.2);
+        assert!((twisted.value(&p) - sphere.value(&p)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn taper_with_zero_rate_matches_the_untransformed_object() {
+        let tapered = Taper::new(Sphere::new(1.0), Vector::new(0.0, 1.0, 0.0), 0.0);
+        let sphere = Sphere::new(1.0);
+        let p = Point::new(0.7, 0.3, 0.2);
+        assert!((tapered.value(&p) - sphere.value(&p)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn bend_maps_world_points_onto_the_expected_arc() {
+        // A plane `x - 1`, so its value directly exposes the straight-space
+        // x coordinate that `Bend::unbend` computes, letting us check the
+        // arc mapping without a second implicit function to reason about.
+        let radius = 2.0;
+        let bent = Bend::new(Plane::new(Vector::new(1.0, 0.0, 0.0), 1.0), radius);
+        let plane = Plane::new(Vector::new(1.0, 0.0, 0.0), 1.0);
+
+        // The world origin sits at the bend's center-facing point, so it
+        // unbends back to the straight-space origin.
+        let origin = Point::new(0.0, 0.0, 0.0);
+        assert!((bent.value(&origin) - plane.value(&Point::new(0.0, 0.0, 0.0))).abs() < 1e-9);
+
+        // A quarter turn around the arc: world (radius, radius, 0) is a
+        // quarter circle away from center (0, radius, 0), which unbends to
+        // straight-space arc length `radius * pi / 2` at zero offset.
+        let quarter = Point::new(radius, radius, 0.0);
+        let expected_x = radius * ::std::f64::consts::FRAC_PI_2;
+        assert!((bent.value(&quarter) - plane.value(&Point::new(expected_x, 0.0, 0.0))).abs() <
+                1e-9);
+    }
+}